@@ -0,0 +1,329 @@
+//! Build script for the `chess` library.
+//!
+//! Pre-computes attack lookup tables at compile time and emits them as `const`/`static` data that
+//! the matching `crate::lut` submodule `include!`s, so that move generation never has to recompute
+//! an attack set at runtime: the magic-bitboard sliding tables for the `Rook` and `Bishop`
+//! ([`crate::lut::sliding`]), and the fixed-offset tables for the `Knight`, `King`, and `Pawn`
+//! ([`crate::lut::knight`], [`crate::lut::king`], [`crate::lut::pawn`]).
+
+use std::env;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+/// Cardinal ray directions (file delta, rank delta) used by the `Rook`.
+const ROOK_DIRS: [(i8, i8); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+/// Diagonal ray directions (file delta, rank delta) used by the `Bishop`.
+const BISHOP_DIRS: [(i8, i8); 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+/// Minimal xorshift64star PRNG, seeded at compile time, used only to search for magic numbers.
+/// Kept self-contained since magic search only ever runs inside the build script.
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545F4914F6CDD1D)
+    }
+
+    /// Returns a sparsely-populated candidate magic number, formed by ANDing a few random u64s
+    /// together, which tends to produce better magics than a uniformly random u64.
+    fn sparse_u64(&mut self) -> u64 {
+        self.next_u64() & self.next_u64() & self.next_u64()
+    }
+}
+
+/// A single square's magic-bitboard entry: the relevant-occupancy mask, the magic multiplier, the
+/// right-shift amount, and this square's starting offset into the piece's flattened attack table.
+struct SquareMagic {
+    mask: u64,
+    magic: u64,
+    shift: u8,
+    offset: usize,
+}
+
+/// Knight move offsets (file delta, rank delta), as a long side of a 1x2 "L" shape.
+const KNIGHT_OFFSETS: [(i8, i8); 8] =
+    [(1, 2), (2, 1), (2, -1), (1, -2), (-1, -2), (-2, -1), (-2, 1), (-1, 2)];
+
+/// King move offsets (file delta, rank delta), the eight squares adjacent to it.
+const KING_OFFSETS: [(i8, i8); 8] =
+    [(1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1), (0, -1), (1, -1)];
+
+fn main() {
+    let out_dir = env::var("OUT_DIR").unwrap();
+
+    let (rook_magics, rook_attacks) = find_magics(rook_mask, ROOK_DIRS, 0x9E3779B97F4A7C15);
+    let (bishop_magics, bishop_attacks) = find_magics(bishop_mask, BISHOP_DIRS, 0xC2B2AE3D27D4EB4F);
+
+    let mut f = File::create(Path::new(&out_dir).join("sliding.rs")).unwrap();
+    write_sliding_table(&mut f, "ROOK", &rook_magics, &rook_attacks);
+    write_sliding_table(&mut f, "BISHOP", &bishop_magics, &bishop_attacks);
+
+    let knight_attacks: Vec<u64> = (0u8..=63).map(|square| offset_attacks(square, &KNIGHT_OFFSETS)).collect();
+    let mut f = File::create(Path::new(&out_dir).join("knight.rs")).unwrap();
+    write_attack_table(&mut f, "KNIGHT_ATTACKS", &knight_attacks);
+
+    let king_attacks: Vec<u64> = (0u8..=63).map(|square| offset_attacks(square, &KING_OFFSETS)).collect();
+    let mut f = File::create(Path::new(&out_dir).join("king.rs")).unwrap();
+    write_attack_table(&mut f, "KING_ATTACKS", &king_attacks);
+
+    let white_pawn_attacks: Vec<u64> = (0u8..=63).map(|square| pawn_attacks(square, true)).collect();
+    let black_pawn_attacks: Vec<u64> = (0u8..=63).map(|square| pawn_attacks(square, false)).collect();
+    let mut f = File::create(Path::new(&out_dir).join("pawn.rs")).unwrap();
+    write_pawn_table(&mut f, &white_pawn_attacks, &black_pawn_attacks);
+
+    let zobrist_keys = ZobristKeys::generate(0x5EED_F00D_1234_5678);
+    let mut f = File::create(Path::new(&out_dir).join("zobrist.rs")).unwrap();
+    write_zobrist_keys(&mut f, &zobrist_keys);
+}
+
+/// Returns the attack set reachable from `square` by stepping each `(file, rank)` offset in
+/// `offsets` exactly once, discarding any offset that would wrap around a board edge.
+fn offset_attacks(square: u8, offsets: &[(i8, i8)]) -> u64 {
+    let file = (square % 8) as i8;
+    let rank = (square / 8) as i8;
+    let mut attacks = 0u64;
+
+    for (df, dr) in offsets {
+        let (f, r) = (file + df, rank + dr);
+        if (0..8).contains(&f) && (0..8).contains(&r) {
+            attacks |= 1 << (r * 8 + f);
+        }
+    }
+
+    attacks
+}
+
+/// Returns the `Pawn`'s diagonal capture attack set from `square`, given whether it is White's.
+fn pawn_attacks(square: u8, white: bool) -> u64 {
+    let dr = if white { 1 } else { -1 };
+    offset_attacks(square, &[(-1, dr), (1, dr)])
+}
+
+/// Computes the relevant occupancy mask for a `Rook` on the specified square, excluding the board
+/// edges (a blocker on the edge never changes the attack set, so it need not be part of the mask).
+fn rook_mask(square: u8) -> u64 {
+    let file = (square % 8) as i8;
+    let rank = (square / 8) as i8;
+    let mut mask = 0u64;
+
+    for r in (rank + 1)..7 { mask |= 1 << (r * 8 + file); }
+    for r in (1..rank).rev() { mask |= 1 << (r * 8 + file); }
+    for f in (file + 1)..7 { mask |= 1 << (rank * 8 + f); }
+    for f in (1..file).rev() { mask |= 1 << (rank * 8 + f); }
+
+    mask
+}
+
+/// Computes the relevant occupancy mask for a `Bishop` on the specified square, excluding the
+/// board edges.
+fn bishop_mask(square: u8) -> u64 {
+    let file = (square % 8) as i8;
+    let rank = (square / 8) as i8;
+    let mut mask = 0u64;
+
+    for (df, dr) in BISHOP_DIRS {
+        let (mut f, mut r) = (file + df, rank + dr);
+        while (1..7).contains(&f) && (1..7).contains(&r) {
+            mask |= 1 << (r * 8 + f);
+            f += df;
+            r += dr;
+        }
+    }
+
+    mask
+}
+
+/// Walks each ray in `dirs` from `square` until it runs off the board or hits a blocker in
+/// `occupancy`, returning the true attack set for that occupancy (unlike the mask, this includes
+/// the board edges and the blocking square itself).
+fn sliding_attacks(square: u8, occupancy: u64, dirs: [(i8, i8); 4]) -> u64 {
+    let file = (square % 8) as i8;
+    let rank = (square / 8) as i8;
+    let mut attacks = 0u64;
+
+    for (df, dr) in dirs {
+        let (mut f, mut r) = (file + df, rank + dr);
+        while (0..8).contains(&f) && (0..8).contains(&r) {
+            let target = 1u64 << (r * 8 + f);
+            attacks |= target;
+            if occupancy & target != 0 {
+                break;
+            }
+            f += df;
+            r += dr;
+        }
+    }
+
+    attacks
+}
+
+/// Searches for a magic multiplier that maps every occupancy subset of `mask` to a collision-free
+/// (or constructively-consistent) index, returning the magic and its populated attack slice.
+fn find_magic(square: u8, mask: u64, bits: u8, dirs: [(i8, i8); 4], rng: &mut Rng) -> (u64, Vec<u64>) {
+    loop {
+        let magic = rng.sparse_u64();
+        let mut table = vec![0u64; 1 << bits];
+        let mut used = vec![false; 1 << bits];
+        let mut subset = 0u64;
+        let mut collided = false;
+
+        loop {
+            let attacks = sliding_attacks(square, subset, dirs);
+            let index = ((subset.wrapping_mul(magic)) >> (64 - bits)) as usize;
+
+            if !used[index] {
+                used[index] = true;
+                table[index] = attacks;
+            } else if table[index] != attacks {
+                collided = true;
+                break;
+            }
+
+            // Carry-rippler trick: enumerates every subset of `mask`, wrapping back to zero.
+            subset = subset.wrapping_sub(mask) & mask;
+            if subset == 0 {
+                break;
+            }
+        }
+
+        if !collided {
+            return (magic, table);
+        }
+    }
+}
+
+/// Finds a magic for every square, returning each square's [`SquareMagic`] alongside the flattened
+/// attack table its `offset` indexes into.
+fn find_magics(mask_fn: fn(u8) -> u64, dirs: [(i8, i8); 4], seed: u64) -> (Vec<SquareMagic>, Vec<u64>) {
+    let mut rng = Rng(seed);
+    let mut magics = Vec::with_capacity(64);
+    let mut attacks = Vec::new();
+
+    for square in 0u8..=63 {
+        let mask = mask_fn(square);
+        let bits = mask.count_ones() as u8;
+        let (magic, table) = find_magic(square, mask, bits, dirs, &mut rng);
+
+        magics.push(SquareMagic { mask, magic, shift: 64 - bits, offset: attacks.len() });
+        attacks.extend(table);
+    }
+
+    (magics, attacks)
+}
+
+/// Writes a piece's `{prefix}_MAGICS` array of [`SquareMagic`] entries and its flattened
+/// `{prefix}_ATTACKS` table in a specified file. The attack table is emitted as a `static` rather
+/// than a `const`, since it is far too large to usefully inline at every use site.
+fn write_sliding_table(file: &mut File, prefix: &str, magics: &[SquareMagic], attacks: &[u64]) {
+    writeln!(file, "const {prefix}_MAGICS: [SlidingMagic; 64] = [").unwrap();
+    for magic in magics {
+        writeln!(
+            file,
+            "    SlidingMagic {{ mask: {}, magic: {}, shift: {}, offset: {} }},",
+            magic.mask, magic.magic, magic.shift, magic.offset,
+        ).unwrap();
+    }
+    writeln!(file, "];").unwrap();
+
+    writeln!(file, "static {prefix}_ATTACKS: [u64; {}] = [", attacks.len()).unwrap();
+    for attack in attacks {
+        writeln!(file, "    {attack},").unwrap();
+    }
+    writeln!(file, "];").unwrap();
+}
+
+/// Writes a `[u64; 64]` attack table as a constant named `name` in a specified file.
+fn write_attack_table(file: &mut File, name: &str, attacks: &[u64]) {
+    writeln!(file, "const {name}: [u64; 64] = [").unwrap();
+    for attack in attacks {
+        writeln!(file, "    {attack},").unwrap();
+    }
+    writeln!(file, "];").unwrap();
+}
+
+/// Writes the `PAWN_ATTACKS` table, indexed `[Color::White as usize | Color::Black as usize][square]`,
+/// as a constant in a specified file.
+fn write_pawn_table(file: &mut File, white_attacks: &[u64], black_attacks: &[u64]) {
+    writeln!(file, "const PAWN_ATTACKS: [[u64; 64]; 2] = [").unwrap();
+    for attacks in [white_attacks, black_attacks] {
+        writeln!(file, "    [").unwrap();
+        for attack in attacks {
+            writeln!(file, "        {attack},").unwrap();
+        }
+        writeln!(file, "    ],").unwrap();
+    }
+    writeln!(file, "];").unwrap();
+}
+
+/// The full table of random keys [`crate::zobrist`] XORs together to produce a position's hash.
+struct ZobristKeys {
+    /// One key per (piece kind, color, square), indexed `[kind + (6 if Black)][square]`.
+    pieces: [[u64; 64]; 12],
+    /// One key per castling right (`WHITE_KINGSIDE`, `WHITE_QUEENSIDE`, `BLACK_KINGSIDE`,
+    /// `BLACK_QUEENSIDE`), indexed by bit position within the castling-rights mask.
+    castling: [u64; 4],
+    /// One key per file, for the en passant target square.
+    en_passant_file: [u64; 8],
+    /// XORed in whenever it is Black's turn to move.
+    side_to_move: u64,
+}
+
+impl ZobristKeys {
+    /// Generates a table of Zobrist keys, seeded so that the keys stay stable across builds.
+    fn generate(seed: u64) -> Self {
+        let mut rng = Rng(seed);
+
+        let mut pieces = [[0u64; 64]; 12];
+        for plane in pieces.iter_mut() {
+            for key in plane.iter_mut() {
+                *key = rng.next_u64();
+            }
+        }
+
+        let mut castling = [0u64; 4];
+        for key in castling.iter_mut() {
+            *key = rng.next_u64();
+        }
+
+        let mut en_passant_file = [0u64; 8];
+        for key in en_passant_file.iter_mut() {
+            *key = rng.next_u64();
+        }
+
+        let side_to_move = rng.next_u64();
+
+        ZobristKeys { pieces, castling, en_passant_file, side_to_move }
+    }
+}
+
+/// Writes the Zobrist key table as constants in a specified file.
+fn write_zobrist_keys(file: &mut File, keys: &ZobristKeys) {
+    writeln!(file, "const PIECE_KEYS: [[u64; 64]; 12] = [").unwrap();
+    for plane in &keys.pieces {
+        writeln!(file, "    [").unwrap();
+        for key in plane {
+            writeln!(file, "        {key},").unwrap();
+        }
+        writeln!(file, "    ],").unwrap();
+    }
+    writeln!(file, "];").unwrap();
+
+    writeln!(file, "const CASTLING_KEYS: [u64; 4] = [").unwrap();
+    for key in &keys.castling {
+        writeln!(file, "    {key},").unwrap();
+    }
+    writeln!(file, "];").unwrap();
+
+    writeln!(file, "const EN_PASSANT_FILE_KEYS: [u64; 8] = [").unwrap();
+    for key in &keys.en_passant_file {
+        writeln!(file, "    {key},").unwrap();
+    }
+    writeln!(file, "];").unwrap();
+
+    writeln!(file, "const SIDE_TO_MOVE_KEY: u64 = {};", keys.side_to_move).unwrap();
+}
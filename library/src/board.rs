@@ -1,9 +1,18 @@
-use crate::{fen, piece::Piece};
+use crate::{
+    fen::{self, InvalidError},
+    movegen,
+    piece::{Color, Kind, Piece},
+};
 use std::fmt;
 
 /// Represents a chessboard.
 pub struct Board {
-    pub(super) squares: [Piece; 64],
+    pub(super) bitboards: [[u64; Kind::NUM_VARIANTS]; Color::NUM_VARIANTS],
+    pub(super) active_color: bool,
+    pub(super) castling_rights: u8,
+    pub(super) en_passant: Option<u8>,
+    pub(super) halfmoves: u16,
+    pub(super) fullmoves: u16,
 }
 
 impl fmt::Display for Board {
@@ -23,18 +32,226 @@ impl Board {
         return fen::parse_fen(fen).unwrap();
     }
 
-    /// Returns a human-readable `String` representation of the `Board`.
+    /// Returns the FEN string describing this `Board`.
+    pub fn to_fen(&self) -> String {
+        fen::generate_fen(self)
+    }
+
+    /// Returns the `Piece` occupying `square`, or `None` if it is empty.
+    pub fn piece_at(&self, square: u8) -> Option<Piece> {
+        let mask = 1u64 << square;
+
+        for color_index in 0..Color::NUM_VARIANTS {
+            for kind_index in 0..Kind::NUM_VARIANTS {
+                if self.bitboards[color_index][kind_index] & mask != 0 {
+                    return Some(Piece {
+                        kind: Kind::try_from_index(kind_index).unwrap(),
+                        color: Color::try_from_index(color_index).unwrap(),
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Returns a bitboard of every square occupied by `color`.
+    pub fn color_occupancy(&self, color: Color) -> u64 {
+        self.bitboards[color as usize].iter().fold(0, |occupancy, bitboard| occupancy | bitboard)
+    }
+
+    /// Returns a bitboard of every occupied square.
+    pub fn occupied(&self) -> u64 {
+        self.color_occupancy(Color::White) | self.color_occupancy(Color::Black)
+    }
+
+    /// Returns an iterator over the index of every occupied square, in ascending order.
+    pub fn squares(&self) -> impl Iterator<Item = u8> {
+        SetBits { remaining: self.occupied() }
+    }
+
+    /// Returns a human-readable `String` representation of the `Board`, rank 8 to rank 1.
     pub fn to_string(&self) -> String {
         let mut board = String::from("");
 
-        for (index, piece) in self.squares.iter().enumerate() {
-            if index > 0 && index % 8 == 0 {
+        for rank in (0u8..8).rev() {
+            if rank != 7 {
                 board += "\n";
             }
 
-            board += &piece.to_string().unwrap();
+            for file in 0u8..8 {
+                board += &match self.piece_at(rank * 8 + file) {
+                    Some(piece) => piece.to_string(),
+                    None => ".".to_string(),
+                };
+            }
         }
 
         return board;
     }
+
+    /// Checks that this position is not just syntactically parseable, but legally possible,
+    /// returning the first `InvalidError` violation found.
+    pub(super) fn validate(&self) -> Result<(), InvalidError> {
+        self.validate_king_counts()?;
+        self.validate_kings_not_adjacent()?;
+        self.validate_no_pawns_on_back_rank()?;
+        self.validate_castling_rights()?;
+        self.validate_en_passant_target()?;
+        self.validate_opponent_not_in_check()?;
+
+        Ok(())
+    }
+
+    /// Each color must have exactly one king on the board.
+    fn validate_king_counts(&self) -> Result<(), InvalidError> {
+        for color in [Color::White, Color::Black] {
+            let count = self.bitboards[color as usize][Kind::King as usize].count_ones() as u8;
+
+            if count != 1 {
+                return Err(InvalidError::WrongKingCount { color, count });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The two kings may not sit on adjacent squares, since each would then be attacking the
+    /// other's square. Assumes each color has exactly one king; run after
+    /// [`Self::validate_king_counts`].
+    fn validate_kings_not_adjacent(&self) -> Result<(), InvalidError> {
+        let white_king = self.bitboards[Color::White as usize][Kind::King as usize].trailing_zeros() as u8;
+        let black_king = self.bitboards[Color::Black as usize][Kind::King as usize].trailing_zeros() as u8;
+
+        let file_diff = (white_king % 8) as i8 - (black_king % 8) as i8;
+        let rank_diff = (white_king / 8) as i8 - (black_king / 8) as i8;
+
+        if file_diff.abs() <= 1 && rank_diff.abs() <= 1 {
+            return Err(InvalidError::KingsAdjacent);
+        }
+
+        Ok(())
+    }
+
+    /// No pawn may sit on the first or last rank, since it would have nowhere to promote from or
+    /// could never have reached that square.
+    fn validate_no_pawns_on_back_rank(&self) -> Result<(), InvalidError> {
+        const BACK_RANKS: u64 = 0xFF000000000000FF;
+
+        let pawns = self.bitboards[Color::White as usize][Kind::Pawn as usize]
+            | self.bitboards[Color::Black as usize][Kind::Pawn as usize];
+        let offenders = pawns & BACK_RANKS;
+
+        if offenders != 0 {
+            return Err(InvalidError::PawnOnBackRank { square: offenders.trailing_zeros() as u8 });
+        }
+
+        Ok(())
+    }
+
+    /// Each claimed castling right must have its king on its color's back rank, and a rook still
+    /// on that same rank on the claimed side of the king. Home squares are derived from the
+    /// king's actual file rather than hardcoded to e1/e8 and the board corners, so Chess960
+    /// starting placements parsed via Shredder-FEN notation validate correctly too.
+    fn validate_castling_rights(&self) -> Result<(), InvalidError> {
+        let checks = [
+            (fen::WHITE_KINGSIDE, Color::White, true),
+            (fen::WHITE_QUEENSIDE, Color::White, false),
+            (fen::BLACK_KINGSIDE, Color::Black, true),
+            (fen::BLACK_QUEENSIDE, Color::Black, false),
+        ];
+
+        for (right, color, kingside) in checks {
+            if self.castling_rights & right == 0 {
+                continue;
+            }
+
+            let back_rank = if color == Color::White { 0u8 } else { 7u8 };
+            let king_square = self.bitboards[color as usize][Kind::King as usize].trailing_zeros() as u8;
+            let king_file = king_square % 8;
+
+            let king_in_place = king_square / 8 == back_rank;
+            let rook_in_place = (0..8).any(|file| {
+                let is_correct_side = if kingside { file > king_file } else { file < king_file };
+                is_correct_side
+                    && self.bitboards[color as usize][Kind::Rook as usize] & (1 << (back_rank * 8 + file)) != 0
+            });
+
+            if !king_in_place || !rook_in_place {
+                return Err(InvalidError::CastlingRightsMismatch { right });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The en passant target, if present, must be empty and sit directly behind a pawn that could
+    /// have just double-pushed past it.
+    fn validate_en_passant_target(&self) -> Result<(), InvalidError> {
+        let target = match self.en_passant {
+            Some(target) => target,
+            None => return Ok(()),
+        };
+
+        let rank = target / 8;
+        let file = target % 8;
+
+        // The side to move can only capture en passant a pawn of the opposite color that just
+        // double-pushed past `target`, so that pawn must sit one rank behind it, having come from
+        // the square one rank beyond the target on the far side.
+        let (pawn_rank, behind_pawn_rank, pawn_color) = if self.active_color {
+            (rank - 1, rank + 1, Color::Black)
+        } else {
+            (rank + 1, rank - 1, Color::White)
+        };
+        let pawn_square = pawn_rank * 8 + file;
+        let behind_pawn_square = behind_pawn_rank * 8 + file;
+
+        if self.occupied() & (1 << target) != 0 {
+            return Err(InvalidError::EnPassantTargetMismatch { target });
+        }
+
+        if self.occupied() & (1 << behind_pawn_square) != 0 {
+            return Err(InvalidError::EnPassantTargetMismatch { target });
+        }
+
+        if self.bitboards[pawn_color as usize][Kind::Pawn as usize] & (1 << pawn_square) == 0 {
+            return Err(InvalidError::EnPassantTargetMismatch { target });
+        }
+
+        Ok(())
+    }
+
+    /// The side not to move must not be in check: it would mean the side to move's previous turn
+    /// left the opponent's king under attack, which is impossible to reach legally.
+    fn validate_opponent_not_in_check(&self) -> Result<(), InvalidError> {
+        let opponent = if self.active_color { Color::Black } else { Color::White };
+
+        if movegen::in_check(self, opponent) {
+            return Err(InvalidError::OpponentInCheck { color: opponent });
+        }
+
+        Ok(())
+    }
+}
+
+/// Iterator over the set bits of a bitboard, from least to most significant, consuming each bit
+/// as it is yielded.
+struct SetBits {
+    remaining: u64,
+}
+
+impl Iterator for SetBits {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let square = self.remaining.trailing_zeros() as u8;
+        self.remaining &= self.remaining - 1;
+
+        Some(square)
+    }
 }
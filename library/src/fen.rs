@@ -1,24 +1,40 @@
+//! Parsing and generating FEN (Forsyth-Edwards Notation) strings.
+
 use core::fmt;
 
-use crate::piece::Piece;
+use crate::piece::{Color, Kind};
 
 use super::board::Board;
 
 pub static FEN_START_POS: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
 pub static FEN_ENDGAME_POS: &str = "8/5k2/3p4/1p1Pp2p/pP2Pp1P/P4P1K/8/8 b - - 99 50";
 
+/// White's kingside castling right, within a castling-rights bitmask.
+pub const WHITE_KINGSIDE: u8 = 0b1000;
+/// White's queenside castling right, within a castling-rights bitmask.
+pub const WHITE_QUEENSIDE: u8 = 0b0100;
+/// Black's kingside castling right, within a castling-rights bitmask.
+pub const BLACK_KINGSIDE: u8 = 0b0010;
+/// Black's queenside castling right, within a castling-rights bitmask.
+pub const BLACK_QUEENSIDE: u8 = 0b0001;
+
 pub enum FenError {
-    NotEnoughFields { fields: usize },
+    InvalidFieldCount { fields: usize },
     IncorrectRankCount { ranks: usize },
     IncorrectFileCount { rank: usize },
     UnrecognizedPiece { piece: char },
+    UnrecognizedActiveColor { color: String },
+    UnrecognizedCastlingRights { castling_rights: String },
+    UnrecognizedEnPassantTarget { target: String },
+    InvalidMoveField { field: String },
+    Invalid(InvalidError),
 }
 
 impl fmt::Debug for FenError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            FenError::NotEnoughFields { fields } => {
-                writeln!(f, "invalid number of fen fields: {fields}, expected 6")
+            FenError::InvalidFieldCount { fields } => {
+                writeln!(f, "invalid number of fen fields: {fields}, expected 1 to 6 (only piece placement is required)")
             }
             FenError::IncorrectRankCount { ranks } => {
                 writeln!(f, "invalid number of ranks: {ranks}, expected 8")
@@ -29,86 +45,318 @@ impl fmt::Debug for FenError {
             FenError::UnrecognizedPiece { piece } => {
                 writeln!(f, "unrecognized piece: {piece}")
             }
+            FenError::UnrecognizedActiveColor { ref color } => {
+                writeln!(f, "unrecognized active color: {color}, expected 'w' or 'b'")
+            }
+            FenError::UnrecognizedCastlingRights { ref castling_rights } => {
+                writeln!(f, "unrecognized castling rights: {castling_rights}, expected '-' or a string containing 'K', 'Q', 'k', 'q', and/or Shredder-FEN file letters 'A'-'H'/'a'-'h'")
+            }
+            FenError::UnrecognizedEnPassantTarget { ref target } => {
+                writeln!(f, "unrecognized en passant target: {target}, expected '-' or a square in algebraic notation")
+            }
+            FenError::InvalidMoveField { ref field } => {
+                writeln!(f, "invalid move field: {field}, expected unsigned 16-bit integer")
+            }
+            FenError::Invalid(ref err) => {
+                writeln!(f, "invalid position: {err:?}")
+            }
+        }
+    }
+}
+
+impl From<InvalidError> for FenError {
+    fn from(err: InvalidError) -> Self {
+        FenError::Invalid(err)
+    }
+}
+
+/// Represents ways a syntactically valid FEN string can still describe an illegal position.
+pub enum InvalidError {
+    WrongKingCount { color: Color, count: u8 },
+    KingsAdjacent,
+    PawnOnBackRank { square: u8 },
+    CastlingRightsMismatch { right: u8 },
+    EnPassantTargetMismatch { target: u8 },
+    OpponentInCheck { color: Color },
+}
+
+impl fmt::Debug for InvalidError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            InvalidError::WrongKingCount { color, count } => {
+                writeln!(f, "{color:?} has {count} kings, expected exactly 1")
+            }
+            InvalidError::KingsAdjacent => {
+                writeln!(f, "the two kings are on adjacent squares")
+            }
+            InvalidError::PawnOnBackRank { square } => {
+                writeln!(f, "pawn on back rank at square {square}")
+            }
+            InvalidError::CastlingRightsMismatch { right } => {
+                writeln!(f, "castling right {right:#06b} does not match the position of its king and rook")
+            }
+            InvalidError::EnPassantTargetMismatch { target } => {
+                writeln!(f, "en passant target {target} is not behind a pawn that could have just double-pushed")
+            }
+            InvalidError::OpponentInCheck { color } => {
+                writeln!(f, "{color:?} is not to move but is in check")
+            }
         }
     }
 }
 
 pub fn parse_fen(fen: &str) -> Result<Board, FenError> {
-    let fields: Vec<&str> = fen.split_whitespace().collect();
+    let mut fields: Vec<&str> = fen.split_whitespace().collect();
 
-    // The FEN string must have exactly 6 fields.
-    if fields.len() != 6 {
-        return Err(FenError::NotEnoughFields {
+    // Only the piece placement field is required. Like mainstream FEN parsers, any trailing
+    // fields left off are filled in with their default values.
+    const TRAILING_DEFAULTS: [&str; 5] = ["w", "-", "-", "0", "1"];
+
+    if fields.is_empty() || fields.len() > 6 {
+        return Err(FenError::InvalidFieldCount {
             fields: fields.len(),
         });
     }
 
-    let squares = parse_piece_placement(fields[0]).unwrap();
+    while fields.len() < 6 {
+        fields.push(TRAILING_DEFAULTS[fields.len() - 1]);
+    }
+
+    let bitboards = parse_piece_placement(fields[0])?;
+    let active_color = parse_active_color(fields[1])?;
+    let castling_rights = parse_castling_rights(fields[2], &bitboards)?;
+    let en_passant = parse_en_passant_target(fields[3], active_color)?;
+    let halfmoves = parse_halfmoves(fields[4])?;
+    let fullmoves = parse_fullmoves(fields[5])?;
 
-    let board = Board { squares };
+    let board = Board {
+        bitboards,
+        active_color,
+        castling_rights,
+        en_passant,
+        halfmoves,
+        fullmoves,
+    };
+
+    board.validate()?;
 
     Ok(board)
 }
 
-fn parse_piece_placement(field: &str) -> Result<[Piece; 64], FenError> {
-    let ranks: Vec<&str> = field.split('/').collect();
+/// Parses the FEN piece placement field, generating each color's per-kind bitboards, indexed
+/// `[Color as usize][Kind as usize]`. Square 0 is a1 and square 63 is h8.
+fn parse_piece_placement(field: &str) -> Result<[[u64; Kind::NUM_VARIANTS]; Color::NUM_VARIANTS], FenError> {
+    // FEN lists ranks from 8 down to 1, but square indices run from rank 1 up to rank 8.
+    let ranks: Vec<&str> = field.split('/').rev().collect();
 
-    // There must exist exactly 8 ranks.
     if ranks.len() != 8 {
         return Err(FenError::IncorrectRankCount { ranks: ranks.len() });
     }
 
-    let mut squares: [Piece; 64] = [Piece { kind: 0, color: 0 }; 64];
+    let mut bitboards = [[0u64; Kind::NUM_VARIANTS]; Color::NUM_VARIANTS];
+
+    for (rank, squares) in ranks.iter().enumerate() {
+        let mut file: u8 = 0;
 
-    // Iterate over the ranks, parsing each character to its corresponding piece.
-    for (i, rank) in ranks.iter().enumerate() {
-        let mut index = i * 8;
-        for c in rank.chars() {
+        for c in squares.chars() {
             // Digits represent the number of consecutive empty squares.
-            if c.is_digit(10) {
-                index += c.to_digit(10).unwrap() as usize;
+            if let Some(digit) = c.to_digit(10) {
+                file += digit as u8;
                 continue;
             }
 
-            let kind = match c {
-                'p' | 'P' => 0b001,
-                'n' | 'N' => 0b010,
-                'b' | 'B' => 0b011,
-                'r' | 'R' => 0b100,
-                'q' | 'Q' => 0b101,
-                'k' | 'K' => 0b110,
+            let kind = match c.to_ascii_lowercase() {
+                'p' => Kind::Pawn,
+                'n' => Kind::Knight,
+                'b' => Kind::Bishop,
+                'r' => Kind::Rook,
+                'q' => Kind::Queen,
+                'k' => Kind::King,
                 _ => return Err(FenError::UnrecognizedPiece { piece: c }),
             };
+            let color = if c.is_uppercase() { Color::White } else { Color::Black };
 
-            let color = match c.is_uppercase() {
-                true => 0b0000,
-                false => 0b1000,
-            };
+            let square = rank as u8 * 8 + file;
+            bitboards[color as usize][kind as usize] |= 1 << square;
+            file += 1;
+        }
 
-            squares[index] = Piece { kind, color };
-            index += 1;
+        if file != 8 {
+            return Err(FenError::IncorrectFileCount { rank });
         }
     }
 
-    Ok(squares)
+    Ok(bitboards)
 }
 
+/// Parses the FEN active color field, returning `true` if White is to move, `false` if Black.
 fn parse_active_color(field: &str) -> Result<bool, FenError> {
-    unimplemented!();
+    match field {
+        "w" => Ok(true),
+        "b" => Ok(false),
+        _ => Err(FenError::UnrecognizedActiveColor { color: field.to_string() }),
+    }
 }
 
-fn parse_castling_rights(field: &str) -> Result<bool, FenError> {
-    unimplemented!();
+/// Parses the FEN castling rights field, returning a bitmask of `WHITE_KINGSIDE`,
+/// `WHITE_QUEENSIDE`, `BLACK_KINGSIDE`, and `BLACK_QUEENSIDE`.
+///
+/// Besides the standard `KQkq` notation, this also accepts Shredder-FEN/X-FEN file-letter
+/// notation (`A`-`H` for White, `a`-`h` for Black), as emitted by some engines and Chess960
+/// tooling, naming the file of the castling rook rather than its side. The named file is mapped
+/// to a side by comparing it to that color's king file in `bitboards`: duplicate or out-of-order
+/// letters collapse onto the same right and don't produce an error.
+fn parse_castling_rights(field: &str, bitboards: &[[u64; Kind::NUM_VARIANTS]; Color::NUM_VARIANTS]) -> Result<u8, FenError> {
+    if field == "-" {
+        return Ok(0);
+    }
+
+    let is_recognized = |c: char| "KQkq".contains(c) || matches!(c, 'A'..='H' | 'a'..='h');
+    if !field.chars().all(is_recognized) {
+        return Err(FenError::UnrecognizedCastlingRights { castling_rights: field.to_string() });
+    }
+
+    let mut rights = 0;
+
+    for c in field.chars() {
+        rights |= match c {
+            'K' => WHITE_KINGSIDE,
+            'Q' => WHITE_QUEENSIDE,
+            'k' => BLACK_KINGSIDE,
+            'q' => BLACK_QUEENSIDE,
+            letter => shredder_castling_right(letter, bitboards),
+        };
+    }
+
+    Ok(rights)
 }
 
-fn parse_en_passant_target(field: &str) -> Result<bool, FenError> {
-    unimplemented!();
+/// Maps a Shredder-FEN rook file letter (`A`-`H` for White, `a`-`h` for Black) to the castling
+/// right it names, by comparing the rook's file to that color's king file in `bitboards`: a rook
+/// east of the king grants the kingside right, one west of the king grants the queenside right.
+fn shredder_castling_right(letter: char, bitboards: &[[u64; Kind::NUM_VARIANTS]; Color::NUM_VARIANTS]) -> u8 {
+    let color = if letter.is_uppercase() { Color::White } else { Color::Black };
+    let rook_file = letter.to_ascii_uppercase() as u8 - b'A';
+    let king_file = bitboards[color as usize][Kind::King as usize].trailing_zeros() as u8 % 8;
+    let kingside = rook_file > king_file;
+
+    match (color, kingside) {
+        (Color::White, true) => WHITE_KINGSIDE,
+        (Color::White, false) => WHITE_QUEENSIDE,
+        (Color::Black, true) => BLACK_KINGSIDE,
+        (Color::Black, false) => BLACK_QUEENSIDE,
+    }
 }
 
-fn parse_halfmoves(field: &str) -> Result<bool, FenError> {
-    unimplemented!();
+/// Parses the FEN en passant target field, returning the target square index (0..=63), or `None`
+/// if there is no en passant target.
+fn parse_en_passant_target(field: &str, active_color: bool) -> Result<Option<u8>, FenError> {
+    if field == "-" {
+        return Ok(None);
+    }
+
+    let mut chars = field.chars();
+    let (file, rank) = match (chars.next(), chars.next(), chars.next()) {
+        (Some(file @ 'a'..='h'), Some(rank @ '1'..='8'), None) => (file, rank),
+        _ => return Err(FenError::UnrecognizedEnPassantTarget { target: field.to_string() }),
+    };
+
+    let file = file as u8 - b'a';
+    let rank = rank as u8 - b'1';
+
+    // The target square must sit on rank 6 when White is to move (Black just double-pushed), or
+    // rank 3 when Black is to move (White just double-pushed).
+    let expected_rank = if active_color { 5 } else { 2 };
+    if rank != expected_rank {
+        return Err(FenError::UnrecognizedEnPassantTarget { target: field.to_string() });
+    }
+
+    Ok(Some(rank * 8 + file))
 }
 
-fn parse_fullmoves(field: &str) -> Result<bool, FenError> {
-    unimplemented!();
+/// Parses the FEN halfmove clock field, returning a `u16`.
+fn parse_halfmoves(field: &str) -> Result<u16, FenError> {
+    field.parse().map_err(|_| FenError::InvalidMoveField { field: field.to_string() })
+}
+
+/// Parses the FEN fullmove number field, returning a `u16`.
+fn parse_fullmoves(field: &str) -> Result<u16, FenError> {
+    field.parse().map_err(|_| FenError::InvalidMoveField { field: field.to_string() })
+}
+
+/// Generates the FEN string describing `board`, in the same 6-field format consumed by
+/// [`parse_fen`].
+pub fn generate_fen(board: &Board) -> String {
+    let piece_placement = generate_piece_placement(board);
+    let active_color = if board.active_color { "w" } else { "b" };
+    let castling_rights = generate_castling_rights(board.castling_rights);
+    let en_passant = generate_en_passant_target(board.en_passant);
+
+    format!(
+        "{piece_placement} {active_color} {castling_rights} {en_passant} {} {}",
+        board.halfmoves, board.fullmoves,
+    )
+}
+
+/// Generates the FEN piece placement field, listing ranks from 8 down to 1 and run-length
+/// encoding consecutive empty squares as digits.
+fn generate_piece_placement(board: &Board) -> String {
+    let mut field = String::new();
+
+    for rank in (0u8..8).rev() {
+        if rank != 7 {
+            field.push('/');
+        }
+
+        let mut empty = 0;
+
+        for file in 0u8..8 {
+            match board.piece_at(rank * 8 + file) {
+                Some(piece) => {
+                    if empty != 0 {
+                        field += &empty.to_string();
+                        empty = 0;
+                    }
+                    field += &piece.to_string();
+                }
+                None => empty += 1,
+            }
+        }
+
+        if empty != 0 {
+            field += &empty.to_string();
+        }
+    }
+
+    field
+}
+
+/// Generates the FEN castling rights field from a `WHITE_KINGSIDE`/`WHITE_QUEENSIDE`/
+/// `BLACK_KINGSIDE`/`BLACK_QUEENSIDE` bitmask, or `"-"` if no rights remain.
+fn generate_castling_rights(castling_rights: u8) -> String {
+    let mut field = String::new();
+
+    if castling_rights & WHITE_KINGSIDE != 0 { field.push('K'); }
+    if castling_rights & WHITE_QUEENSIDE != 0 { field.push('Q'); }
+    if castling_rights & BLACK_KINGSIDE != 0 { field.push('k'); }
+    if castling_rights & BLACK_QUEENSIDE != 0 { field.push('q'); }
+
+    if field.is_empty() {
+        field.push('-');
+    }
+
+    field
+}
+
+/// Generates the FEN en passant target field, or `"-"` if there is no target square.
+fn generate_en_passant_target(en_passant: Option<u8>) -> String {
+    match en_passant {
+        Some(square) => {
+            let file = (b'a' + square % 8) as char;
+            let rank = (b'1' + square / 8) as char;
+
+            format!("{file}{rank}")
+        }
+        None => "-".to_string(),
+    }
 }
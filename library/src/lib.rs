@@ -0,0 +1,6 @@
+pub mod board;
+pub mod fen;
+pub mod lut;
+pub mod movegen;
+pub mod piece;
+pub mod zobrist;
@@ -0,0 +1,8 @@
+//! Compile-time `King` attack lookup table, generated at build time into `$OUT_DIR/king.rs`.
+
+include!(concat!(env!("OUT_DIR"), "/king.rs"));
+
+/// Returns the `King`'s attack set from `square`.
+pub fn king_attacks(square: u8) -> u64 {
+    KING_ATTACKS[square as usize]
+}
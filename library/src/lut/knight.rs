@@ -0,0 +1,8 @@
+//! Compile-time `Knight` attack lookup table, generated at build time into `$OUT_DIR/knight.rs`.
+
+include!(concat!(env!("OUT_DIR"), "/knight.rs"));
+
+/// Returns the `Knight`'s attack set from `square`.
+pub fn knight_attacks(square: u8) -> u64 {
+    KNIGHT_ATTACKS[square as usize]
+}
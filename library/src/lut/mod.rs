@@ -0,0 +1,7 @@
+//! Compile-time lookup tables (LUTs) for piece movement, generated by `build.rs` wherever
+//! computing an attack set at runtime would be wasted work on every call.
+
+pub mod sliding;
+pub mod knight;
+pub mod king;
+pub mod pawn;
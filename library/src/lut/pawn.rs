@@ -0,0 +1,11 @@
+//! Compile-time `Pawn` capture attack lookup table, generated at build time into
+//! `$OUT_DIR/pawn.rs`.
+
+use crate::piece::Color;
+
+include!(concat!(env!("OUT_DIR"), "/pawn.rs"));
+
+/// Returns the `Pawn`'s diagonal capture attack set from `square`, given `color`.
+pub fn pawn_attacks(color: Color, square: u8) -> u64 {
+    PAWN_ATTACKS[color as usize][square as usize]
+}
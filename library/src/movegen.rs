@@ -0,0 +1,405 @@
+//! Legal move generation, make/unmake, and a `perft` correctness harness built on top of `Board`.
+
+use crate::{
+    board::Board,
+    fen,
+    lut::{king, knight, pawn, sliding},
+    piece::{Color, Kind},
+};
+
+/// A single chess move.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Move {
+    pub from: u8,
+    pub to: u8,
+    pub promotion: Option<Kind>,
+    pub capture: bool,
+    pub castle: bool,
+    pub en_passant: bool,
+}
+
+/// The minimal state needed to undo a `Move` applied via [`Board::apply_move`], without cloning
+/// the entire board.
+pub struct Undo {
+    captured: Option<(Kind, Color)>,
+    castling_rights: u8,
+    en_passant: Option<u8>,
+    halfmoves: u16,
+}
+
+fn opposite(color: Color) -> Color {
+    match color {
+        Color::White => Color::Black,
+        Color::Black => Color::White,
+    }
+}
+
+fn file(square: u8) -> u8 {
+    square % 8
+}
+
+fn rank(square: u8) -> u8 {
+    square / 8
+}
+
+/// Returns `true` if `square` is attacked by any piece of color `by`.
+pub fn is_square_attacked(board: &Board, square: u8, by: Color) -> bool {
+    let occupancy = board.occupied();
+
+    if knight::knight_attacks(square) & board.bitboards[by as usize][Kind::Knight as usize] != 0 {
+        return true;
+    }
+
+    if king::king_attacks(square) & board.bitboards[by as usize][Kind::King as usize] != 0 {
+        return true;
+    }
+
+    if pawn::pawn_attacks(opposite(by), square) & board.bitboards[by as usize][Kind::Pawn as usize] != 0 {
+        return true;
+    }
+
+    let rooks_queens = board.bitboards[by as usize][Kind::Rook as usize]
+        | board.bitboards[by as usize][Kind::Queen as usize];
+    if sliding::rook_attacks(square, occupancy) & rooks_queens != 0 {
+        return true;
+    }
+
+    let bishops_queens = board.bitboards[by as usize][Kind::Bishop as usize]
+        | board.bitboards[by as usize][Kind::Queen as usize];
+    if sliding::bishop_attacks(square, occupancy) & bishops_queens != 0 {
+        return true;
+    }
+
+    false
+}
+
+/// Returns the square of `color`'s king.
+fn king_square(board: &Board, color: Color) -> u8 {
+    board.bitboards[color as usize][Kind::King as usize].trailing_zeros() as u8
+}
+
+/// Returns `true` if `color`'s king is currently in check.
+pub fn in_check(board: &Board, color: Color) -> bool {
+    is_square_attacked(board, king_square(board, color), opposite(color))
+}
+
+/// Generates every pseudo-legal move for the side to move: legal by the movement rules of each
+/// piece, but not yet filtered for leaving the mover's own king in check.
+fn pseudo_legal_moves(board: &Board) -> Vec<Move> {
+    let color = if board.active_color { Color::White } else { Color::Black };
+    let own = board.color_occupancy(color);
+    let enemy = board.color_occupancy(opposite(color));
+    let occupancy = own | enemy;
+    let mut moves = Vec::new();
+
+    generate_pawn_moves(board, color, occupancy, enemy, &mut moves);
+
+    for square in bits(board.bitboards[color as usize][Kind::Knight as usize]) {
+        push_targets(square, knight::knight_attacks(square) & !own, enemy, &mut moves);
+    }
+
+    for square in bits(board.bitboards[color as usize][Kind::Bishop as usize]) {
+        push_targets(square, sliding::bishop_attacks(square, occupancy) & !own, enemy, &mut moves);
+    }
+
+    for square in bits(board.bitboards[color as usize][Kind::Rook as usize]) {
+        push_targets(square, sliding::rook_attacks(square, occupancy) & !own, enemy, &mut moves);
+    }
+
+    for square in bits(board.bitboards[color as usize][Kind::Queen as usize]) {
+        push_targets(square, sliding::queen_attacks(square, occupancy) & !own, enemy, &mut moves);
+    }
+
+    let king_sq = king_square(board, color);
+    push_targets(king_sq, king::king_attacks(king_sq) & !own, enemy, &mut moves);
+    generate_castling_moves(board, color, occupancy, &mut moves);
+
+    moves
+}
+
+/// Returns an iterator over the set bits of a bitboard, from least to most significant.
+fn bits(mut bitboard: u64) -> impl Iterator<Item = u8> {
+    std::iter::from_fn(move || {
+        if bitboard == 0 {
+            return None;
+        }
+        let square = bitboard.trailing_zeros() as u8;
+        bitboard &= bitboard - 1;
+        Some(square)
+    })
+}
+
+fn push_targets(from: u8, targets: u64, enemy: u64, moves: &mut Vec<Move>) {
+    for to in bits(targets) {
+        moves.push(Move {
+            from,
+            to,
+            promotion: None,
+            capture: enemy & (1 << to) != 0,
+            castle: false,
+            en_passant: false,
+        });
+    }
+}
+
+fn generate_pawn_moves(board: &Board, color: Color, occupancy: u64, enemy: u64, moves: &mut Vec<Move>) {
+    const PROMOTIONS: [Kind; 4] = [Kind::Queen, Kind::Rook, Kind::Bishop, Kind::Knight];
+
+    let (dr, start_rank, promotion_rank): (i8, u8, u8) = match color {
+        Color::White => (1, 1, 7),
+        Color::Black => (-1, 6, 0),
+    };
+
+    for from in bits(board.bitboards[color as usize][Kind::Pawn as usize]) {
+        let f = file(from) as i8;
+        let r = rank(from) as i8;
+
+        let push_rank = r + dr;
+        if (0..8).contains(&push_rank) {
+            let push_to = (push_rank * 8 + f) as u8;
+            if occupancy & (1 << push_to) == 0 {
+                push_pawn_move(from, push_to, rank(push_to) == promotion_rank, false, moves, &PROMOTIONS);
+
+                if rank(from) == start_rank {
+                    let double_rank = push_rank + dr;
+                    let double_to = (double_rank * 8 + f) as u8;
+                    if occupancy & (1 << double_to) == 0 {
+                        moves.push(Move { from, to: double_to, promotion: None, capture: false, castle: false, en_passant: false });
+                    }
+                }
+            }
+        }
+
+        for df in [-1i8, 1] {
+            let nf = f + df;
+            if !(0..8).contains(&nf) {
+                continue;
+            }
+            let to = (push_rank * 8 + nf) as u8;
+            if !(0..8).contains(&push_rank) {
+                continue;
+            }
+
+            if enemy & (1 << to) != 0 {
+                push_pawn_move(from, to, rank(to) == promotion_rank, true, moves, &PROMOTIONS);
+            } else if board.en_passant == Some(to) {
+                moves.push(Move { from, to, promotion: None, capture: true, castle: false, en_passant: true });
+            }
+        }
+    }
+}
+
+fn push_pawn_move(from: u8, to: u8, promotes: bool, capture: bool, moves: &mut Vec<Move>, promotions: &[Kind; 4]) {
+    if promotes {
+        for &promotion in promotions {
+            moves.push(Move { from, to, promotion: Some(promotion), capture, castle: false, en_passant: false });
+        }
+    } else {
+        moves.push(Move { from, to, promotion: None, capture, castle: false, en_passant: false });
+    }
+}
+
+fn generate_castling_moves(board: &Board, color: Color, occupancy: u64, moves: &mut Vec<Move>) {
+    let (kingside, queenside, home, enemy) = match color {
+        Color::White => (fen::WHITE_KINGSIDE, fen::WHITE_QUEENSIDE, 4u8, Color::Black),
+        Color::Black => (fen::BLACK_KINGSIDE, fen::BLACK_QUEENSIDE, 60u8, Color::White),
+    };
+
+    if board.castling_rights & kingside != 0
+        && occupancy & ((1 << (home + 1)) | (1 << (home + 2))) == 0
+        && !is_square_attacked(board, home, enemy)
+        && !is_square_attacked(board, home + 1, enemy)
+        && !is_square_attacked(board, home + 2, enemy)
+    {
+        moves.push(Move { from: home, to: home + 2, promotion: None, capture: false, castle: true, en_passant: false });
+    }
+
+    if board.castling_rights & queenside != 0
+        && occupancy & ((1 << (home - 1)) | (1 << (home - 2)) | (1 << (home - 3))) == 0
+        && !is_square_attacked(board, home, enemy)
+        && !is_square_attacked(board, home - 1, enemy)
+        && !is_square_attacked(board, home - 2, enemy)
+    {
+        moves.push(Move { from: home, to: home - 2, promotion: None, capture: false, castle: true, en_passant: false });
+    }
+}
+
+/// Generates every legal move for the side to move, by generating pseudo-legal moves and
+/// discarding any that leave the mover's own king in check.
+pub fn legal_moves(board: &mut Board) -> Vec<Move> {
+    let color = if board.active_color { Color::White } else { Color::Black };
+
+    pseudo_legal_moves(board)
+        .into_iter()
+        .filter(|&mv| {
+            let undo = board.apply_move(mv);
+            let legal = !in_check(board, color);
+            board.undo_move(mv, undo);
+            legal
+        })
+        .collect()
+}
+
+impl Board {
+    /// Applies `mv` to this `Board` in place, returning an [`Undo`] record that [`Board::undo_move`]
+    /// can use to restore the prior state without cloning the whole board.
+    pub fn apply_move(&mut self, mv: Move) -> Undo {
+        let color = if self.active_color { Color::White } else { Color::Black };
+        let moving_kind = self.piece_at(mv.from).expect("move must start on an occupied square").kind;
+
+        let undo = Undo {
+            captured: if mv.en_passant {
+                Some((Kind::Pawn, opposite(color)))
+            } else {
+                self.piece_at(mv.to).map(|piece| (piece.kind, piece.color))
+            },
+            castling_rights: self.castling_rights,
+            en_passant: self.en_passant,
+            halfmoves: self.halfmoves,
+        };
+
+        // Remove any captured piece first, including the en passant victim, which does not sit on
+        // the destination square.
+        if mv.en_passant {
+            let captured_square = if color == Color::White { mv.to - 8 } else { mv.to + 8 };
+            self.bitboards[opposite(color) as usize][Kind::Pawn as usize] &= !(1 << captured_square);
+        } else if mv.capture {
+            let (captured_kind, captured_color) = undo.captured.unwrap();
+            self.bitboards[captured_color as usize][captured_kind as usize] &= !(1 << mv.to);
+        }
+
+        self.bitboards[color as usize][moving_kind as usize] &= !(1 << mv.from);
+        self.bitboards[color as usize][mv.promotion.unwrap_or(moving_kind) as usize] |= 1 << mv.to;
+
+        if mv.castle {
+            let (rook_from, rook_to) = if mv.to > mv.from {
+                (mv.from + 3, mv.from + 1)
+            } else {
+                (mv.from - 4, mv.from - 1)
+            };
+            self.bitboards[color as usize][Kind::Rook as usize] &= !(1 << rook_from);
+            self.bitboards[color as usize][Kind::Rook as usize] |= 1 << rook_to;
+        }
+
+        self.castling_rights &= !castling_rights_lost(mv.from, mv.to);
+
+        self.en_passant = if moving_kind == Kind::Pawn && mv.from.abs_diff(mv.to) == 16 {
+            Some((mv.from + mv.to) / 2)
+        } else {
+            None
+        };
+
+        self.halfmoves = if moving_kind == Kind::Pawn || mv.capture { 0 } else { self.halfmoves + 1 };
+
+        if color == Color::Black {
+            self.fullmoves += 1;
+        }
+
+        self.active_color = !self.active_color;
+
+        undo
+    }
+
+    /// Restores this `Board` to the state it was in before `mv` was applied via
+    /// [`Board::apply_move`].
+    pub fn undo_move(&mut self, mv: Move, undo: Undo) {
+        self.active_color = !self.active_color;
+        let color = if self.active_color { Color::White } else { Color::Black };
+
+        if color == Color::Black {
+            self.fullmoves -= 1;
+        }
+
+        let moved_kind = mv.promotion.unwrap_or_else(|| {
+            self.piece_at(mv.to).expect("moved piece must be on its destination square").kind
+        });
+
+        self.bitboards[color as usize][moved_kind as usize] &= !(1 << mv.to);
+        self.bitboards[color as usize][Kind::Pawn as usize] |= if mv.promotion.is_some() { 1 << mv.from } else { 0 };
+        if mv.promotion.is_none() {
+            self.bitboards[color as usize][moved_kind as usize] |= 1 << mv.from;
+        }
+
+        if mv.castle {
+            let (rook_from, rook_to) = if mv.to > mv.from {
+                (mv.from + 3, mv.from + 1)
+            } else {
+                (mv.from - 4, mv.from - 1)
+            };
+            self.bitboards[color as usize][Kind::Rook as usize] &= !(1 << rook_to);
+            self.bitboards[color as usize][Kind::Rook as usize] |= 1 << rook_from;
+        }
+
+        if mv.en_passant {
+            let captured_square = if color == Color::White { mv.to - 8 } else { mv.to + 8 };
+            self.bitboards[opposite(color) as usize][Kind::Pawn as usize] |= 1 << captured_square;
+        } else if let Some((captured_kind, captured_color)) = undo.captured {
+            self.bitboards[captured_color as usize][captured_kind as usize] |= 1 << mv.to;
+        }
+
+        self.castling_rights = undo.castling_rights;
+        self.en_passant = undo.en_passant;
+        self.halfmoves = undo.halfmoves;
+    }
+}
+
+/// Returns the castling rights that are forfeited when a piece moves from `from` to `to` (because
+/// a king or rook left its home square, or a rook was captured on its home square).
+fn castling_rights_lost(from: u8, to: u8) -> u8 {
+    let mut lost = 0;
+
+    for square in [from, to] {
+        lost |= match square {
+            4 => fen::WHITE_KINGSIDE | fen::WHITE_QUEENSIDE,
+            0 => fen::WHITE_QUEENSIDE,
+            7 => fen::WHITE_KINGSIDE,
+            60 => fen::BLACK_KINGSIDE | fen::BLACK_QUEENSIDE,
+            56 => fen::BLACK_QUEENSIDE,
+            63 => fen::BLACK_KINGSIDE,
+            _ => 0,
+        };
+    }
+
+    lost
+}
+
+/// Recursively counts the number of leaf nodes reachable from `board` in exactly `depth` plies,
+/// the standard correctness harness for move generation.
+pub fn perft(board: &mut Board, depth: u8) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+
+    let moves = legal_moves(board);
+
+    if depth == 1 {
+        return moves.len() as u64;
+    }
+
+    let mut nodes = 0;
+    for mv in moves {
+        let undo = board.apply_move(mv);
+        nodes += perft(board, depth - 1);
+        board.undo_move(mv, undo);
+    }
+
+    nodes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Published perft node counts for the start position, depth 1 through 5, as listed on the
+    /// [Chess Programming Wiki](https://www.chessprogramming.org/Perft_Results#Initial_Position).
+    #[test]
+    fn test_perft_start_pos() {
+        let mut board = Board::new();
+
+        assert_eq!(perft(&mut board, 1), 20);
+        assert_eq!(perft(&mut board, 2), 400);
+        assert_eq!(perft(&mut board, 3), 8_902);
+        assert_eq!(perft(&mut board, 4), 197_281);
+        assert_eq!(perft(&mut board, 5), 4_865_609);
+    }
+}
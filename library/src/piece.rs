@@ -1,46 +1,76 @@
-use std::fmt;
+/// Represents a type of chess piece.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    Pawn,
+    Knight,
+    Bishop,
+    Rook,
+    Queen,
+    King,
+}
+
+impl Kind {
+    /// The number of `Kind` variants, and therefore the size of any table indexed by `Kind`.
+    pub const NUM_VARIANTS: usize = 6;
 
-pub enum PieceError {
-    UnrecognizedPiece { piece: u8 },
-    UnrecognizedColor { color: u8 },
+    /// Recovers the `Kind` whose discriminant is `index`, or `None` if out of range.
+    pub fn try_from_index(index: usize) -> Option<Self> {
+        match index {
+            0 => Some(Kind::Pawn),
+            1 => Some(Kind::Knight),
+            2 => Some(Kind::Bishop),
+            3 => Some(Kind::Rook),
+            4 => Some(Kind::Queen),
+            5 => Some(Kind::King),
+            _ => None,
+        }
+    }
 }
 
-impl fmt::Debug for PieceError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            PieceError::UnrecognizedPiece { piece } => {
-                writeln!(f, "unrecognized piece: {piece}")
-            }
-            PieceError::UnrecognizedColor { color } => {
-                writeln!(f, "unrecognized color: {color}")
-            }
+/// Represents a color of a chess piece.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Color {
+    White,
+    Black,
+}
+
+impl Color {
+    /// The number of `Color` variants, and therefore the size of any table indexed by `Color`.
+    pub const NUM_VARIANTS: usize = 2;
+
+    /// Recovers the `Color` whose discriminant is `index`, or `None` if out of range.
+    pub fn try_from_index(index: usize) -> Option<Self> {
+        match index {
+            0 => Some(Color::White),
+            1 => Some(Color::Black),
+            _ => None,
         }
     }
 }
 
-#[derive(Clone, Copy)]
+/// Represents a chess piece.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Piece {
-    pub kind: u8,
-    pub color: u8,
+    pub kind: Kind,
+    pub color: Color,
 }
 
 impl Piece {
-    pub fn to_string(&self) -> Result<String, PieceError> {
+    /// Returns a human-readable `String` representation of this piece: a single letter, uppercase
+    /// for White and lowercase for Black.
+    pub fn to_string(&self) -> String {
         let piece = match self.kind {
-            0b000 => ".",
-            0b001 => "p",
-            0b010 => "n",
-            0b011 => "b",
-            0b100 => "r",
-            0b101 => "q",
-            0b110 => "k",
-            _ => return Err(PieceError::UnrecognizedPiece { piece: self.kind }),
+            Kind::Pawn => "p",
+            Kind::Knight => "n",
+            Kind::Bishop => "b",
+            Kind::Rook => "r",
+            Kind::Queen => "q",
+            Kind::King => "k",
         };
 
         match self.color {
-            0b0000 => return Ok(piece.to_uppercase()),
-            0b1000 => return Ok(piece.to_lowercase()),
-            _ => return Err(PieceError::UnrecognizedColor { color: self.color }),
+            Color::White => piece.to_uppercase(),
+            Color::Black => piece.to_lowercase(),
         }
     }
 }
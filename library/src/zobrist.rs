@@ -0,0 +1,66 @@
+//! Zobrist hashing for `Board`, giving every position a 64-bit hash so downstream search code can
+//! build a transposition table and detect repetitions.
+//!
+//! The key table is generated at build time into `$OUT_DIR/zobrist.rs`, like the other lookup
+//! tables in [`crate::lut`], so hashing never pays for key generation at runtime.
+
+use crate::{board::Board, piece::{Color, Piece}};
+
+include!(concat!(env!("OUT_DIR"), "/zobrist.rs"));
+
+/// Maps a piece's kind and color to its plane within the piece-square key table.
+fn piece_plane(piece: Piece) -> usize {
+    let color_offset = if piece.color == Color::Black { 6 } else { 0 };
+    piece.kind as usize + color_offset
+}
+
+impl Board {
+    /// Computes the Zobrist hash of this position from scratch, by XORing together the key for
+    /// every occupied square plus the applicable castling, en passant, and side-to-move keys.
+    pub fn zobrist_hash(&self) -> u64 {
+        let mut hash = 0u64;
+
+        for square in self.squares() {
+            let piece = self.piece_at(square).unwrap();
+            hash = toggle_piece(hash, piece, square);
+        }
+
+        for right in 0u8..4 {
+            if self.castling_rights & (1 << right) != 0 {
+                hash = toggle_castling(hash, right);
+            }
+        }
+
+        if let Some(target) = self.en_passant {
+            hash = toggle_en_passant(hash, target % 8);
+        }
+
+        if !self.active_color {
+            hash = flip_side(hash);
+        }
+
+        hash
+    }
+}
+
+/// XORs the key for `piece` on `square` into `hash`, so calling this twice with the same
+/// arguments restores the original hash.
+pub fn toggle_piece(hash: u64, piece: Piece, square: u8) -> u64 {
+    hash ^ PIECE_KEYS[piece_plane(piece)][square as usize]
+}
+
+/// XORs the key for the given castling right (its bit position within the castling-rights mask)
+/// into `hash`.
+pub fn toggle_castling(hash: u64, right: u8) -> u64 {
+    hash ^ CASTLING_KEYS[right as usize]
+}
+
+/// XORs the key for the given en passant file (0..=7) into `hash`.
+pub fn toggle_en_passant(hash: u64, file: u8) -> u64 {
+    hash ^ EN_PASSANT_FILE_KEYS[file as usize]
+}
+
+/// XORs the side-to-move key into `hash`.
+pub fn flip_side(hash: u64) -> u64 {
+    hash ^ SIDE_TO_MOVE_KEY
+}
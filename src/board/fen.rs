@@ -16,6 +16,7 @@ pub enum FenError {
     IncorrectSquareCount { count: u8 },
     UnrecognizedActiveColor { color: String },
     UnrecognizedCastlingRights { castling_rights: String },
+    InvalidEnPassant { target: String },
     InvalidMoveField { moves: String }
 }
 
@@ -43,6 +44,9 @@ impl fmt::Debug for FenError {
             FenError::UnrecognizedCastlingRights { castling_rights } => {
                 writeln!(f, "unrecognized castling rights: {castling_rights}, expected '-' or a string containing 'K', 'Q', 'k', and/or 'q'")
             }
+            FenError::InvalidEnPassant { target } => {
+                writeln!(f, "invalid en passant target: {target}, expected '-' or a square behind an enemy pawn that just double-pushed")
+            }
             FenError::InvalidMoveField { moves } => {
                 writeln!(f, "invalid move field: {moves}, expected unsigned 16-bit integer")
             }
@@ -52,7 +56,7 @@ impl fmt::Debug for FenError {
 
 /// Parses a FEN string, returning a tuple of chess data on the current position. In the event the
 /// given FEN string is invalid or unrecognizable, a `FenError` is returned.
-pub fn parse_fen(fen: &str) -> Result<([[u64; 6]; 2], Color, [CastleRights; 2], u16, u16), FenError> {
+pub fn parse_fen(fen: &str) -> Result<([[u64; 6]; 2], Color, [CastleRights; 2], Option<u8>, u16, u16), FenError> {
 
         // [ piece placement, active color, castling rights, en passant target, halfmoves, fullmoves ]
         let fen: Vec<&str> = fen.split_whitespace().collect();
@@ -63,11 +67,11 @@ pub fn parse_fen(fen: &str) -> Result<([[u64; 6]; 2], Color, [CastleRights; 2],
         let bitboards = parse_piece_placement(fen[0]).unwrap();
         let active_color = parse_active_color(fen[1]).unwrap();
         let castling_rights = parse_castling_rights(fen[2]).unwrap();
-        // TODO: Parse and return en passant targets
+        let en_passant = parse_enpassant_target(fen[3], &active_color, &bitboards)?;
         let halfmoves = parse_move_count(fen[4]).unwrap();
         let fullmoves = parse_move_count(fen[5]).unwrap();
 
-        Ok((bitboards, active_color, castling_rights, halfmoves, fullmoves))
+        Ok((bitboards, active_color, castling_rights, en_passant, halfmoves, fullmoves))
 
 }
 
@@ -153,9 +157,55 @@ fn parse_castling_rights(castling_rights: &str) -> Result<[CastleRights; 2], Fen
     Ok([white_castling, black_castling])
 }
 
-#[allow(dead_code, unused_variables)]
-fn parse_enpassant_target(enpassant_target: &str) {
-    todo!();
+/// Parse FEN en passant target string, returning the target square index (0..=63), or `None` if
+/// there is no en passant target.
+fn parse_enpassant_target(enpassant_target: &str, active_color: &Color, bitboards: &[[u64; 6]; 2]) -> Result<Option<u8>, FenError> {
+    if enpassant_target == "-" {
+        return Ok(None);
+    }
+
+    let mut chars = enpassant_target.chars();
+    let (file, rank) = match (chars.next(), chars.next(), chars.next()) {
+        (Some(file @ 'a'..='h'), Some(rank @ '1'..='8'), None) => (file, rank),
+        _ => return Err(FenError::InvalidEnPassant { target: enpassant_target.to_string() }),
+    };
+
+    let file = file as u8 - b'a';
+    let rank = rank as u8 - b'1';
+
+    // The target must sit on rank 6 (index 5) when White is to move, since that's the square
+    // behind a Black pawn that just double-pushed, or rank 3 (index 2) when Black is to move.
+    let expected_rank = if *active_color == Color::White { 5 } else { 2 };
+    if rank != expected_rank {
+        return Err(FenError::InvalidEnPassant { target: enpassant_target.to_string() });
+    }
+
+    // Continuing from the target toward the side to move first reaches the pawn itself; continuing
+    // the other way, past the target, reaches the empty square it started behind.
+    let target_square = rank * 8 + file;
+    let (pawn_square, behind_pawn_square) = if *active_color == Color::White {
+        (target_square - 8, target_square + 8)
+    } else {
+        (target_square + 8, target_square - 8)
+    };
+
+    let occupied = bitboards[Color::White as usize].iter().fold(0, |acc, b| acc | b)
+        | bitboards[Color::Black as usize].iter().fold(0, |acc, b| acc | b);
+
+    if occupied & (1 << target_square) != 0 {
+        return Err(FenError::InvalidEnPassant { target: enpassant_target.to_string() });
+    }
+
+    if occupied & (1 << behind_pawn_square) != 0 {
+        return Err(FenError::InvalidEnPassant { target: enpassant_target.to_string() });
+    }
+
+    let enemy_color = if *active_color == Color::White { Color::Black } else { Color::White };
+    if bitboards[enemy_color as usize][Piece::Pawn as usize] & (1 << pawn_square) == 0 {
+        return Err(FenError::InvalidEnPassant { target: enpassant_target.to_string() });
+    }
+
+    Ok(Some(target_square))
 }
 
 /// Parse FEN move count string, returning a `u16`.
@@ -177,12 +227,14 @@ mod tests {
             bitboards,
             active_color,
             castling_rights,
+            en_passant,
             halfmoves,
             fullmoves
         ) = parse_fen(fen).unwrap();
-        
+
         assert_eq!(active_color, Color::White);
         assert_eq!(castling_rights, [CastleRights::Both, CastleRights::Both]);
+        assert_eq!(en_passant, None);
         assert_eq!(halfmoves, 0);
         assert_eq!(fullmoves, 1);
         
@@ -208,12 +260,14 @@ mod tests {
             bitboards,
             active_color,
             castling_rights,
+            en_passant,
             halfmoves,
             fullmoves
         ) = parse_fen(fen).unwrap();
 
         assert_eq!(active_color, Color::Black);
         assert_eq!(castling_rights, [CastleRights::Both, CastleRights::Both]);
+        assert_eq!(en_passant, Some(19));
         assert_eq!(halfmoves, 0);
         assert_eq!(fullmoves, 3);
         
@@ -239,12 +293,14 @@ mod tests {
             bitboards,
             active_color,
             castling_rights,
+            en_passant,
             halfmoves,
             fullmoves
         ) = parse_fen(fen).unwrap();
 
         assert_eq!(active_color, Color::White);
         assert_eq!(castling_rights, [CastleRights::None, CastleRights::None]);
+        assert_eq!(en_passant, None);
         assert_eq!(halfmoves, 2);
         assert_eq!(fullmoves, 21);
         
@@ -270,12 +326,14 @@ mod tests {
             bitboards,
             active_color,
             castling_rights,
+            en_passant,
             halfmoves,
             fullmoves
         ) = parse_fen(fen).unwrap();
 
         assert_eq!(active_color, Color::White);
         assert_eq!(castling_rights, [CastleRights::None, CastleRights::None]);
+        assert_eq!(en_passant, None);
         assert_eq!(halfmoves, 1);
         assert_eq!(fullmoves, 43);
         
@@ -336,6 +394,35 @@ mod tests {
         parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w Kjkq - 0 1").unwrap();
     }
 
+    #[test]
+    #[should_panic(expected = "invalid en passant target: e3, expected '-' or a square behind an enemy pawn that just double-pushed")]
+    fn test_parse_fen_invalid_en_passant_wrong_rank() {
+        parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq e3 0 1").unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid en passant target: d6, expected '-' or a square behind an enemy pawn that just double-pushed")]
+    fn test_parse_fen_invalid_en_passant_no_pawn() {
+        parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq d6 0 1").unwrap();
+    }
+
+    #[test]
+    fn test_parse_fen_en_passant_ignores_square_in_front_of_pawn() {
+        // A decoy on d4 sits in front of the Black pawn on d5, not behind it, so it must not be
+        // mistaken for an occupied double-push origin and reject the target.
+        let fen = "8/8/8/3p4/3P4/8/8/8 w - d6 0 1";
+        let (
+            _,
+            _,
+            _,
+            en_passant,
+            _,
+            _
+        ) = parse_fen(fen).unwrap();
+
+        assert_eq!(en_passant, Some(43));
+    }
+
     #[test]
     #[should_panic(expected = "invalid move field: -1, expected unsigned 16-bit integer")]
     fn test_parse_fen_invalid_halfmove_field() {
@@ -47,6 +47,7 @@ pub struct Board {
     bitboards: [[u64; 6]; 2],
     active_color: Color,
     castling_rights: [CastleRights; 2],
+    en_passant: Option<u8>,
     halfmoves: u16,
     fullmoves: u16
 }
@@ -64,11 +65,12 @@ impl Board {
             bitboards,
             active_color,
             castling_rights,
+            en_passant,
             halfmoves,
             fullmoves
         ) = fen::parse_fen(fen).unwrap();
 
-        Board { bitboards, active_color, castling_rights, halfmoves, fullmoves }
+        Board { bitboards, active_color, castling_rights, en_passant, halfmoves, fullmoves }
     }
 
     pub fn apply_move(&self) {
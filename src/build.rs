@@ -6,6 +6,7 @@
 
 use crate::tables::king::*;
 use crate::tables::knight::*;
+use crate::tables::magic::*;
 use crate::tables::pawn::*;
 use crate::tables::sliding::*;
 
@@ -19,6 +20,8 @@ fn main() {
     populate_knight_moves();
     populate_pawn_moves();
     populate_first_rank_moves();
+    populate_rook_magics();
+    populate_bishop_magics();
 
     let out_dir = env::var("OUT_DIR").unwrap();
     let dest_path = Path::new(&out_dir).join("move_tables.rs");
@@ -28,4 +31,6 @@ fn main() {
     write_knight_moves(&mut f);
     write_pawn_moves(&mut f);
     write_first_rank_moves(&mut f);
+    write_rook_magics(&mut f);
+    write_bishop_magics(&mut f);
 }
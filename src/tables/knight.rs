@@ -1,6 +1,6 @@
 //! Module for computing moveset lookup table, or array, for the `Knight` piece.
 
-use std::{ fs, io };
+use std::{ fs, io::Write };
 
 use crate::board::{FILE_MASKS, File};
 
@@ -18,7 +18,13 @@ pub fn populate_knight_moves() {
 
 /// Writes moveset lookup table for the `Knight` piece as a constant in a specified file.
 pub fn write_knight_moves(file: &mut fs::File) {
-    todo!()
+    writeln!(file, "pub const KNIGHT_MOVES: [u64; 64] = [").unwrap();
+    unsafe {
+        for mv in KNIGHT_MOVES {
+            writeln!(file, "    {mv},").unwrap();
+        }
+    }
+    writeln!(file, "];").unwrap();
 }
 
 /// Given a specified square index, computes moveset bitboard for the `Knight` piece.
@@ -0,0 +1,239 @@
+//! Module for computing magic-bitboard moveset lookup tables, or arrays, for sliding pieces
+//! (`Rook`, `Bishop`, and by extension `Queen`).
+//!
+//! Unlike [`super::sliding`], which reduces any file, rank, or diagonal to a first-rank lookup via
+//! bit-twiddling, magic bitboards index directly into a per-square attack table using a multiply
+//! and shift, at the cost of a larger table.
+
+use std::{ fs, io::Write };
+
+/// Size of each square's slice of the `Rook` attack table: two to the power of the maximum number
+/// of relevant occupancy bits (12) for a `Rook` on any square.
+const ROOK_TABLE_SIZE: usize = 1 << 12;
+
+/// Size of each square's slice of the `Bishop` attack table: two to the power of the maximum
+/// number of relevant occupancy bits (9) for a `Bishop` on any square.
+const BISHOP_TABLE_SIZE: usize = 1 << 9;
+
+/// Relevant occupancy mask for the `Rook` on every square, excluding board edges.
+static mut ROOK_MASKS: [u64; 64] = [0; 64];
+
+/// Relevant occupancy mask for the `Bishop` on every square, excluding board edges.
+static mut BISHOP_MASKS: [u64; 64] = [0; 64];
+
+/// Magic multiplier for the `Rook` on every square.
+static mut ROOK_MAGICS: [u64; 64] = [0; 64];
+
+/// Magic multiplier for the `Bishop` on every square.
+static mut BISHOP_MAGICS: [u64; 64] = [0; 64];
+
+/// Right-shift amount (`64 - relevant bits`) for the `Rook` on every square.
+static mut ROOK_SHIFTS: [u8; 64] = [0; 64];
+
+/// Right-shift amount (`64 - relevant bits`) for the `Bishop` on every square.
+static mut BISHOP_SHIFTS: [u8; 64] = [0; 64];
+
+/// Per-square `[square][magic index]` attack table for the `Rook`.
+static mut ROOK_ATTACKS: [[u64; ROOK_TABLE_SIZE]; 64] = [[0; ROOK_TABLE_SIZE]; 64];
+
+/// Per-square `[square][magic index]` attack table for the `Bishop`.
+static mut BISHOP_ATTACKS: [[u64; BISHOP_TABLE_SIZE]; 64] = [[0; BISHOP_TABLE_SIZE]; 64];
+
+/// Cardinal ray directions (file delta, rank delta) used by the `Rook`.
+const ROOK_DIRS: [(i8, i8); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+/// Diagonal ray directions (file delta, rank delta) used by the `Bishop`.
+const BISHOP_DIRS: [(i8, i8); 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+/// Minimal xorshift64star PRNG, seeded at compile time, used only to search for magic numbers.
+/// Kept self-contained since magic search only ever runs inside the build script.
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545F4914F6CDD1D)
+    }
+
+    /// Returns a sparsely-populated candidate magic number, formed by ANDing a few random u64s
+    /// together, which tends to produce better magics than a uniformly random u64.
+    fn sparse_u64(&mut self) -> u64 {
+        self.next_u64() & self.next_u64() & self.next_u64()
+    }
+}
+
+/// Populates the magic-bitboard tables for the `Rook`.
+pub fn populate_rook_magics() {
+    let mut rng = Rng(0x9E3779B97F4A7C15);
+    for square in 0u8..=63 {
+        let mask = rook_mask(square);
+        let bits = mask.count_ones() as u8;
+        let (magic, attacks) = find_magic(square, mask, bits, ROOK_DIRS, &mut rng);
+        unsafe {
+            ROOK_MASKS[square as usize] = mask;
+            ROOK_MAGICS[square as usize] = magic;
+            ROOK_SHIFTS[square as usize] = 64 - bits;
+            ROOK_ATTACKS[square as usize][..attacks.len()].copy_from_slice(&attacks);
+        }
+    }
+}
+
+/// Populates the magic-bitboard tables for the `Bishop`.
+pub fn populate_bishop_magics() {
+    let mut rng = Rng(0xC2B2AE3D27D4EB4F);
+    for square in 0u8..=63 {
+        let mask = bishop_mask(square);
+        let bits = mask.count_ones() as u8;
+        let (magic, attacks) = find_magic(square, mask, bits, BISHOP_DIRS, &mut rng);
+        unsafe {
+            BISHOP_MASKS[square as usize] = mask;
+            BISHOP_MAGICS[square as usize] = magic;
+            BISHOP_SHIFTS[square as usize] = 64 - bits;
+            BISHOP_ATTACKS[square as usize][..attacks.len()].copy_from_slice(&attacks);
+        }
+    }
+}
+
+/// Writes the `Rook` magic-bitboard tables as constants in a specified file.
+pub fn write_rook_magics(file: &mut fs::File) {
+    write_magic_tables(file, "ROOK", unsafe { &ROOK_MASKS }, unsafe { &ROOK_MAGICS }, unsafe { &ROOK_SHIFTS }, unsafe { &ROOK_ATTACKS });
+}
+
+/// Writes the `Bishop` magic-bitboard tables as constants in a specified file.
+pub fn write_bishop_magics(file: &mut fs::File) {
+    write_magic_tables(file, "BISHOP", unsafe { &BISHOP_MASKS }, unsafe { &BISHOP_MAGICS }, unsafe { &BISHOP_SHIFTS }, unsafe { &BISHOP_ATTACKS });
+}
+
+/// Shared writer for a piece's mask, magic, shift, and flattened attack constants.
+fn write_magic_tables<const N: usize>(
+    file: &mut fs::File,
+    prefix: &str,
+    masks: &[u64; 64],
+    magics: &[u64; 64],
+    shifts: &[u8; 64],
+    attacks: &[[u64; N]; 64],
+) {
+    let table_size = N;
+    writeln!(file, "pub const {prefix}_MASKS: [u64; 64] = [").unwrap();
+    for mask in masks {
+        writeln!(file, "    {mask},").unwrap();
+    }
+    writeln!(file, "];").unwrap();
+
+    writeln!(file, "pub const {prefix}_MAGICS: [u64; 64] = [").unwrap();
+    for magic in magics {
+        writeln!(file, "    {magic},").unwrap();
+    }
+    writeln!(file, "];").unwrap();
+
+    writeln!(file, "pub const {prefix}_SHIFTS: [u8; 64] = [").unwrap();
+    for shift in shifts {
+        writeln!(file, "    {shift},").unwrap();
+    }
+    writeln!(file, "];").unwrap();
+
+    writeln!(file, "pub const {prefix}_ATTACKS: [[u64; {table_size}]; 64] = [").unwrap();
+    for square in attacks {
+        writeln!(file, "    [").unwrap();
+        for attack in square {
+            writeln!(file, "        {attack},").unwrap();
+        }
+        writeln!(file, "    ],").unwrap();
+    }
+    writeln!(file, "];").unwrap();
+}
+
+/// Computes the relevant occupancy mask for a `Rook` on the specified square, excluding the board
+/// edges (a blocker on the edge never changes the attack set, so it need not be part of the mask).
+fn rook_mask(square: u8) -> u64 {
+    let file = (square % 8) as i8;
+    let rank = (square / 8) as i8;
+    let mut mask = 0u64;
+
+    for r in (rank + 1)..7 { mask |= 1 << (r * 8 + file); }
+    for r in (1..rank).rev() { mask |= 1 << (r * 8 + file); }
+    for f in (file + 1)..7 { mask |= 1 << (rank * 8 + f); }
+    for f in (1..file).rev() { mask |= 1 << (rank * 8 + f); }
+
+    mask
+}
+
+/// Computes the relevant occupancy mask for a `Bishop` on the specified square, excluding the
+/// board edges.
+fn bishop_mask(square: u8) -> u64 {
+    let file = (square % 8) as i8;
+    let rank = (square / 8) as i8;
+    let mut mask = 0u64;
+
+    for (df, dr) in BISHOP_DIRS {
+        let (mut f, mut r) = (file + df, rank + dr);
+        while (1..7).contains(&f) && (1..7).contains(&r) {
+            mask |= 1 << (r * 8 + f);
+            f += df;
+            r += dr;
+        }
+    }
+
+    mask
+}
+
+/// Walks each ray in `dirs` from `square` until it runs off the board or hits a blocker in
+/// `occupancy`, returning the true attack set for that occupancy (unlike the mask, this includes
+/// the board edges and the blocking square itself).
+fn sliding_attacks(square: u8, occupancy: u64, dirs: [(i8, i8); 4]) -> u64 {
+    let file = (square % 8) as i8;
+    let rank = (square / 8) as i8;
+    let mut attacks = 0u64;
+
+    for (df, dr) in dirs {
+        let (mut f, mut r) = (file + df, rank + dr);
+        while (0..8).contains(&f) && (0..8).contains(&r) {
+            let target = 1u64 << (r * 8 + f);
+            attacks |= target;
+            if occupancy & target != 0 {
+                break;
+            }
+            f += df;
+            r += dr;
+        }
+    }
+
+    attacks
+}
+
+/// Searches for a magic multiplier that maps every occupancy subset of `mask` to a collision-free
+/// (or constructively-consistent) index, returning the magic and its populated attack table.
+fn find_magic(square: u8, mask: u64, bits: u8, dirs: [(i8, i8); 4], rng: &mut Rng) -> (u64, Vec<u64>) {
+    loop {
+        let magic = rng.sparse_u64();
+        let mut table = vec![0u64; 1 << bits];
+        let mut used = vec![false; 1 << bits];
+        let mut subset = 0u64;
+        let mut collided = false;
+
+        loop {
+            let attacks = sliding_attacks(square, subset, dirs);
+            let index = ((subset.wrapping_mul(magic)) >> (64 - bits)) as usize;
+
+            if !used[index] {
+                used[index] = true;
+                table[index] = attacks;
+            } else if table[index] != attacks {
+                collided = true;
+                break;
+            }
+
+            // Carry-rippler trick: enumerates every subset of `mask`, wrapping back to zero.
+            subset = subset.wrapping_sub(mask) & mask;
+            if subset == 0 {
+                break;
+            }
+        }
+
+        if !collided {
+            return (magic, table);
+        }
+    }
+}
@@ -4,6 +4,7 @@
 mod bitscan;
 
 pub mod sliding;
+pub mod magic;
 pub mod pawn;
 pub mod knight;
 pub mod king;
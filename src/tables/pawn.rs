@@ -1,6 +1,6 @@
 //! Module for computing moveset lookup tables, or arrays, for the `Pawn` piece.
 
-use std::{ fs, io };
+use std::{ fs, io::Write };
 
 use crate::board::{FILE_MASKS, RANK_MASKS, File, Rank, Color};
 
@@ -24,7 +24,29 @@ pub fn populate_pawn_moves() {
 
 /// Writes moveset lookup tables for the `Pawn` piece as constants in a specified file.
 pub fn write_pawn_moves(file: &mut fs::File) {
-    todo!()
+    writeln!(file, "pub const PAWN_QUIET_MOVES: [[u64; 64]; 2] = [").unwrap();
+    unsafe {
+        for moves in PAWN_QUIET_MOVES {
+            writeln!(file, "    [").unwrap();
+            for mv in moves {
+                writeln!(file, "        {mv},").unwrap();
+            }
+            writeln!(file, "    ],").unwrap();
+        }
+    }
+    writeln!(file, "];").unwrap();
+
+    writeln!(file, "pub const PAWN_ATTACKING_MOVES: [[u64; 64]; 2] = [").unwrap();
+    unsafe {
+        for moves in PAWN_ATTACKING_MOVES {
+            writeln!(file, "    [").unwrap();
+            for mv in moves {
+                writeln!(file, "        {mv},").unwrap();
+            }
+            writeln!(file, "    ],").unwrap();
+        }
+    }
+    writeln!(file, "];").unwrap();
 }
 
 /// Given a specified square index and piece color, computes quiet moveset bitboard for the `Pawn`
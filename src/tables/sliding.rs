@@ -1,6 +1,6 @@
 //! Module for computing moveset lookup table, or array, for sliding pieces on the first rank.
 
-use std::{ fs, io };
+use std::{ fs, io::Write };
 use super::bitscan::{ bitscan_forward, bitscan_reverse };
 
 /// Lookup table for sliding piece moves for every square and occupancy on the first rank.
@@ -8,7 +8,9 @@ use super::bitscan::{ bitscan_forward, bitscan_reverse };
 /// Although possible to store sliding piece moves for every square and occupancy on *every* file,
 /// rank, and diagonal, a significant amount of memory is saved by the fact we can represent any
 /// file, rank, and diagonal on the first rank via bit-twiddling, or more specifically flips and
-/// rotation.
+/// rotation. [`rank_attacks`], [`file_attacks`], [`diagonal_attacks`], and
+/// [`anti_diagonal_attacks`] perform this reduction with shifts and masked multiplications rather
+/// than flips and rotations.
 static mut FIRST_RANK_MOVES: [[u8; 256]; 8] = [[0; 256]; 8];
 
 /// Populates moveset lookup table for sliding pieces on the first rank.
@@ -25,12 +27,112 @@ pub fn populate_first_rank_moves() {
 /// Writes moveset lookup table for sliding pieces on the first rank as a constant in a specified
 /// file.
 pub fn write_first_rank_moves(file: &mut fs::File) {
-    todo!()
+    writeln!(file, "pub const FIRST_RANK_MOVES: [[u8; 256]; 8] = [").unwrap();
+    unsafe {
+        for occupancies in FIRST_RANK_MOVES {
+            writeln!(file, "    [").unwrap();
+            for mv in occupancies {
+                writeln!(file, "        {mv},").unwrap();
+            }
+            writeln!(file, "    ],").unwrap();
+        }
+    }
+    writeln!(file, "];").unwrap();
+}
+
+/// Bitmask of the A-file, used to isolate one bit per rank when gathering/scattering files.
+const FILE_A: u64 = 0x0101010101010101;
+
+/// The a1-h8 diagonal, used to gather/scatter files and "/"-diagonals onto the first rank.
+const DIAG_A1H8: u64 = 0x8040201008040201;
+
+/// The h1-a8 anti-diagonal, used to gather/scatter "\"-diagonals onto the first rank.
+const ANTI_DIAG_H1A8: u64 = 0x0102040810204080;
+
+/// Computes the moveset bitboard for a sliding piece along the rank containing `square`, given the
+/// full-board `occupancy`, by reducing to a first-rank lookup.
+pub fn rank_attacks(square: u8, occupancy: u64) -> u64 {
+    let rank = square / 8;
+    let file = square % 8;
+
+    let rank_occupancy = ((occupancy >> (rank * 8)) & 0xFF) as u8;
+    let attacks = unsafe { FIRST_RANK_MOVES[file as usize][rank_occupancy as usize] } as u64;
+
+    attacks << (rank * 8)
+}
+
+/// Computes the moveset bitboard for a sliding piece along the file containing `square`, given the
+/// full-board `occupancy`, by reducing to a first-rank lookup via the Kindergarten multiplication
+/// trick.
+pub fn file_attacks(square: u8, occupancy: u64) -> u64 {
+    let file = square % 8;
+    let rank = square / 8;
+
+    // Gathering via `DIAG_A1H8` packs rank `r` into bit `7 - r` of the index, so the h1-a8
+    // anti-diagonal is used instead to keep the gathered occupancy in rank order.
+    let file_occupancy = (occupancy >> file) & FILE_A;
+    let index = (file_occupancy.wrapping_mul(ANTI_DIAG_H1A8) >> 56) as usize;
+    let attacks = unsafe { FIRST_RANK_MOVES[rank as usize][index] };
+
+    // No single multiply scatters a compact byte back onto the widely-spaced `FILE_A` bits
+    // without collisions, so the result is built up one rank at a time instead.
+    let mut moves = 0;
+    for target_rank in 0u8..8 {
+        if attacks & (1 << target_rank) != 0 {
+            moves |= 1 << (target_rank * 8 + file);
+        }
+    }
+
+    moves
+}
+
+/// Computes the moveset bitboard for a sliding piece along the a1-h8-style diagonal containing
+/// `square`, given the full-board `occupancy`, by reducing to a first-rank lookup.
+pub fn diagonal_attacks(square: u8, occupancy: u64) -> u64 {
+    let file = square % 8;
+    let mask = diagonal_mask(square);
+
+    let index = ((occupancy & mask).wrapping_mul(FILE_A) >> 56) as usize;
+    let attacks = unsafe { FIRST_RANK_MOVES[file as usize][index] } as u64;
+
+    attacks.wrapping_mul(FILE_A) & mask
+}
+
+/// Computes the moveset bitboard for a sliding piece along the h1-a8-style anti-diagonal
+/// containing `square`, given the full-board `occupancy`, by reducing to a first-rank lookup.
+pub fn anti_diagonal_attacks(square: u8, occupancy: u64) -> u64 {
+    let file = square % 8;
+    let mask = anti_diagonal_mask(square);
+
+    let index = ((occupancy & mask).wrapping_mul(FILE_A) >> 56) as usize;
+    let attacks = unsafe { FIRST_RANK_MOVES[file as usize][index] } as u64;
+
+    attacks.wrapping_mul(FILE_A) & mask
+}
+
+/// Computes the bitmask of the a1-h8-style diagonal passing through the specified square.
+fn diagonal_mask(square: u8) -> u64 {
+    let sq = square as i32;
+    let diag = 8 * (sq & 7) - (sq & 56);
+    let north = (-diag) & (diag >> 31);
+    let south = diag & ((-diag) >> 31);
+
+    (DIAG_A1H8 >> south as u32) << north as u32
+}
+
+/// Computes the bitmask of the h1-a8-style anti-diagonal passing through the specified square.
+fn anti_diagonal_mask(square: u8) -> u64 {
+    let sq = square as i32;
+    let diag = 56 - 8 * (sq & 7) - (sq & 56);
+    let north = (-diag) & (diag >> 31);
+    let south = diag & ((-diag) >> 31);
+
+    (ANTI_DIAG_H1A8 >> south as u32) << north as u32
 }
 
 /// Given a specified square index, computes moveset bitboard for a sliding piece on the first rank
 /// of specified occupancy.
-fn compute_first_rank_moves(square: u8, occupancy: u8) -> u8 {    
+fn compute_first_rank_moves(square: u8, occupancy: u8) -> u8 {
     let left_ray = |x: u8| -> u8 { x - 1 };
     let right_ray = |x: u8| -> u8 { !x & !(x - 1) };
 
@@ -50,3 +152,109 @@ fn compute_first_rank_moves(square: u8, occupancy: u8) -> u8 {
 
     left_attacks ^ right_attacks
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Computes the moveset bitboard for a rook-like slider along `square`'s file by walking the
+    /// ray one square at a time, stopping at (and including) the first blocker.
+    fn file_attacks_by_ray_walk(square: u8, occupancy: u64) -> u64 {
+        let file = square % 8;
+        let rank = square / 8;
+
+        let mut attacks = 0;
+
+        for target_rank in (rank + 1)..8 {
+            let target = target_rank * 8 + file;
+            attacks |= 1 << target;
+            if occupancy & (1 << target) != 0 {
+                break;
+            }
+        }
+
+        for target_rank in (0..rank).rev() {
+            let target = target_rank * 8 + file;
+            attacks |= 1 << target;
+            if occupancy & (1 << target) != 0 {
+                break;
+            }
+        }
+
+        attacks
+    }
+
+    /// Computes the moveset bitboard for a slider along `square` by walking each ray in `dirs` one
+    /// square at a time, stopping at (and including) the first blocker.
+    fn ray_walk_attacks(square: u8, occupancy: u64, dirs: [(i8, i8); 2]) -> u64 {
+        let file = (square % 8) as i8;
+        let rank = (square / 8) as i8;
+        let mut attacks = 0u64;
+
+        for (df, dr) in dirs {
+            let (mut f, mut r) = (file + df, rank + dr);
+            while (0..8).contains(&f) && (0..8).contains(&r) {
+                let target = 1u64 << (r * 8 + f);
+                attacks |= target;
+                if occupancy & target != 0 {
+                    break;
+                }
+                f += df;
+                r += dr;
+            }
+        }
+
+        attacks
+    }
+
+    /// Exercises a sliding attack function against the ray-walk reference across every square and
+    /// a batch of pseudo-random occupancies.
+    fn assert_matches_ray_walk(attacks_fn: impl Fn(u8, u64) -> u64, dirs: [(i8, i8); 2]) {
+        populate_first_rank_moves();
+
+        let mut rng: u64 = 0x243F6A8885A308D3;
+        let mut next_occupancy = || {
+            rng ^= rng << 13;
+            rng ^= rng >> 7;
+            rng ^= rng << 17;
+            rng
+        };
+
+        for square in 0u8..64 {
+            for _ in 0..200 {
+                let occupancy = next_occupancy();
+                assert_eq!(attacks_fn(square, occupancy), ray_walk_attacks(square, occupancy, dirs));
+            }
+        }
+    }
+
+    #[test]
+    fn test_file_attacks_matches_ray_walk() {
+        populate_first_rank_moves();
+
+        let mut rng: u64 = 0x243F6A8885A308D3;
+        let mut next_occupancy = || {
+            rng ^= rng << 13;
+            rng ^= rng >> 7;
+            rng ^= rng << 17;
+            rng
+        };
+
+        for square in 0u8..64 {
+            for _ in 0..200 {
+                let occupancy = next_occupancy();
+                assert_eq!(file_attacks(square, occupancy), file_attacks_by_ray_walk(square, occupancy));
+            }
+        }
+    }
+
+    #[test]
+    fn test_diagonal_attacks_matches_ray_walk() {
+        assert_matches_ray_walk(diagonal_attacks, [(1, 1), (-1, -1)]);
+    }
+
+    #[test]
+    fn test_anti_diagonal_attacks_matches_ray_walk() {
+        assert_matches_ray_walk(anti_diagonal_attacks, [(1, -1), (-1, 1)]);
+    }
+}